@@ -1,4 +1,6 @@
-use hacl_star_sys::{Hacl_Curve25519_51_ecdh, Hacl_Curve25519_51_secret_to_public};
+use hacl_star_sys::{
+    Hacl_Curve25519_51_ecdh, Hacl_Curve25519_51_secret_to_public, Hacl_Hash_SHA2_hash_256,
+};
 
 #[derive(Debug, PartialEq, Eq, Clone, Copy)]
 pub enum Error {
@@ -25,6 +27,37 @@ pub fn ecdh(private_key: &[u8; 32], public_key: &[u8; 32]) -> Result<[u8; 32], E
     }
 }
 
+/// Compute the ECDH shared secret and run the shared point through `kdf`,
+/// returning the hashed secret instead of the raw 32 byte point.
+///
+/// This moves the security-critical hashing of the shared point off the
+/// caller and behind a pluggable key-derivation closure.
+#[must_use]
+pub fn ecdh_hashed<const N: usize, F>(
+    private_key: &[u8; 32],
+    public_key: &[u8; 32],
+    mut kdf: F,
+) -> Result<[u8; N], Error>
+where
+    F: FnMut(&[u8]) -> [u8; N],
+{
+    let shared = ecdh(private_key, public_key)?;
+    Ok(kdf(&shared))
+}
+
+/// Compute the ECDH shared secret and hash the shared point with SHA-256,
+/// yielding a 32 byte secret. A safe default that never hands the raw point
+/// to the caller.
+#[must_use]
+pub fn ecdh_sha256(private_key: &[u8; 32], public_key: &[u8; 32]) -> Result<[u8; 32], Error> {
+    ecdh_hashed(private_key, public_key, |point| {
+        let mut out = [0u8; 32];
+        let mut point = point.to_vec();
+        unsafe { Hacl_Hash_SHA2_hash_256(out.as_mut_ptr(), point.as_mut_ptr(), point.len() as u32) };
+        out
+    })
+}
+
 /// Compute the public key for the provided `private_key` (scalar multiplication
 /// with the base point).
 ///
@@ -36,6 +69,71 @@ pub fn secret_to_public(private_key: &[u8; 32]) -> [u8; 32] {
     public
 }
 
+/// An X25519 private key (a 32 byte scalar).
+#[derive(Debug, Clone)]
+pub struct PrivateKey([u8; 32]);
+
+/// An X25519 public key (a 32 byte point).
+#[derive(Debug, Clone)]
+pub struct PublicKey([u8; 32]);
+
+impl From<&[u8; 32]> for PrivateKey {
+    fn from(bytes: &[u8; 32]) -> Self {
+        PrivateKey(*bytes)
+    }
+}
+
+impl TryFrom<&[u8]> for PrivateKey {
+    type Error = Error;
+
+    fn try_from(bytes: &[u8]) -> Result<Self, Error> {
+        let bytes: [u8; 32] = bytes.try_into().map_err(|_| Error::InvalidInput)?;
+        Ok(PrivateKey(bytes))
+    }
+}
+
+impl AsRef<[u8]> for PrivateKey {
+    fn as_ref(&self) -> &[u8] {
+        &self.0
+    }
+}
+
+impl From<&[u8; 32]> for PublicKey {
+    fn from(bytes: &[u8; 32]) -> Self {
+        PublicKey(*bytes)
+    }
+}
+
+impl TryFrom<&[u8]> for PublicKey {
+    type Error = Error;
+
+    fn try_from(bytes: &[u8]) -> Result<Self, Error> {
+        let bytes: [u8; 32] = bytes.try_into().map_err(|_| Error::InvalidInput)?;
+        Ok(PublicKey(bytes))
+    }
+}
+
+impl AsRef<[u8]> for PublicKey {
+    fn as_ref(&self) -> &[u8] {
+        &self.0
+    }
+}
+
+/// Generate an X25519 key pair, sampling the private scalar from `rng` and
+/// clamping it as required for Curve25519.
+pub fn generate_key_pair<R: rand_core::CryptoRng + rand_core::RngCore>(
+    rng: &mut R,
+) -> (PrivateKey, PublicKey) {
+    let mut scalar = [0u8; 32];
+    rng.fill_bytes(&mut scalar);
+    scalar[0] &= 248;
+    scalar[31] &= 127;
+    scalar[31] |= 64;
+
+    let public = secret_to_public(&scalar);
+    (PrivateKey(scalar), PublicKey(public))
+}
+
 #[cfg(all(bmi2, adx, target_arch = "x86_64"))]
 mod vale {
     use hacl_star_sys::Hacl_Curve25519_64_ecdh;
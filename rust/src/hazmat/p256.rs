@@ -1,6 +1,7 @@
 use hacl_star_sys::{
-    Hacl_P256_compressed_to_raw, Hacl_P256_dh_initiator, Hacl_P256_dh_responder,
-    Hacl_P256_uncompressed_to_raw, Hacl_P256_validate_private_key, Hacl_P256_validate_public_key,
+    Hacl_Hash_SHA2_hash_256, Hacl_P256_compressed_to_raw, Hacl_P256_dh_initiator,
+    Hacl_P256_dh_responder, Hacl_P256_uncompressed_to_raw, Hacl_P256_validate_private_key,
+    Hacl_P256_validate_public_key,
 };
 
 #[derive(Debug, PartialEq, Eq, Clone, Copy)]
@@ -115,6 +116,37 @@ pub fn ecdh(private_key: &[u8; 32], public_key: &[u8; 64]) -> Result<[u8; 64], E
     }
 }
 
+/// Compute the ECDH shared secret and run the point coordinates through
+/// `kdf`, returning the hashed secret rather than the raw 64 byte `X || Y`.
+///
+/// This moves the security-critical hashing of the shared point off the
+/// caller and behind a pluggable key-derivation closure.
+#[must_use]
+pub fn ecdh_hashed<const N: usize, F>(
+    private_key: &[u8; 32],
+    public_key: &[u8; 64],
+    mut kdf: F,
+) -> Result<[u8; N], Error>
+where
+    F: FnMut(&[u8], &[u8]) -> [u8; N],
+{
+    let shared = ecdh(private_key, public_key)?;
+    Ok(kdf(&shared[..32], &shared[32..]))
+}
+
+/// Compute the ECDH shared secret and hash its X coordinate with SHA-256,
+/// yielding a 32 byte secret. A safe default that never hands the raw curve
+/// point to the caller.
+#[must_use]
+pub fn ecdh_sha256(private_key: &[u8; 32], public_key: &[u8; 64]) -> Result<[u8; 32], Error> {
+    ecdh_hashed(private_key, public_key, |x, _y| {
+        let mut out = [0u8; 32];
+        let mut x = x.to_vec();
+        unsafe { Hacl_Hash_SHA2_hash_256(out.as_mut_ptr(), x.as_mut_ptr(), x.len() as u32) };
+        out
+    })
+}
+
 /// Compute the public key for the provided `private_key`.
 ///
 /// Returns the 64 bytes public key.
@@ -130,14 +162,85 @@ pub fn secret_to_public(s: &[u8; 32]) -> Result<[u8; 64], Error> {
     }
 }
 
+/// A P256 private key (a 32 byte scalar).
+#[derive(Debug, Clone)]
+pub struct PrivateKey([u8; 32]);
+
+/// A P256 public key (the 64 byte `X || Y`).
+#[derive(Debug, Clone)]
+pub struct PublicKey([u8; 64]);
+
+impl From<&[u8; 32]> for PrivateKey {
+    fn from(bytes: &[u8; 32]) -> Self {
+        PrivateKey(*bytes)
+    }
+}
+
+impl TryFrom<&[u8]> for PrivateKey {
+    type Error = Error;
+
+    fn try_from(bytes: &[u8]) -> Result<Self, Error> {
+        let bytes: [u8; 32] = bytes.try_into().map_err(|_| Error::InvalidInput)?;
+        Ok(PrivateKey(bytes))
+    }
+}
+
+impl AsRef<[u8]> for PrivateKey {
+    fn as_ref(&self) -> &[u8] {
+        &self.0
+    }
+}
+
+impl From<&[u8; 64]> for PublicKey {
+    fn from(bytes: &[u8; 64]) -> Self {
+        PublicKey(*bytes)
+    }
+}
+
+impl TryFrom<&[u8]> for PublicKey {
+    type Error = Error;
+
+    fn try_from(bytes: &[u8]) -> Result<Self, Error> {
+        let bytes: [u8; 64] = bytes.try_into().map_err(|_| Error::InvalidInput)?;
+        validate_point(&bytes)?;
+        Ok(PublicKey(bytes))
+    }
+}
+
+impl AsRef<[u8]> for PublicKey {
+    fn as_ref(&self) -> &[u8] {
+        &self.0
+    }
+}
+
+/// Generate a P256 key pair, sampling the private scalar from `rng` with
+/// rejection sampling against [`validate_scalar`].
+pub fn generate_key_pair<R: rand_core::CryptoRng + rand_core::RngCore>(
+    rng: &mut R,
+) -> (PrivateKey, PublicKey) {
+    loop {
+        let mut scalar = [0u8; 32];
+        rng.fill_bytes(&mut scalar);
+        if validate_scalar(&scalar).is_err() {
+            continue;
+        }
+        // A valid scalar always has a public key, so this never loops twice.
+        if let Ok(public) = secret_to_public(&scalar) {
+            return (PrivateKey(scalar), PublicKey(public));
+        }
+    }
+}
+
 /// ECDSA on P256
 pub mod ecdsa {
     use hacl_star_sys::{
         Hacl_P256_ecdsa_sign_p256_sha2, Hacl_P256_ecdsa_sign_p256_sha384,
-        Hacl_P256_ecdsa_sign_p256_sha512,
+        Hacl_P256_ecdsa_sign_p256_sha512, Hacl_P256_ecdsa_verif_p256_sha2,
+        Hacl_P256_ecdsa_verif_p256_sha384, Hacl_P256_ecdsa_verif_p256_sha512,
     };
 
-    use super::{validate_scalar, validate_scalar_slice, Error};
+    use super::{validate_point, validate_scalar, validate_scalar_slice, Error};
+    use rfc6979::{Hash, Rfc6979};
 
     macro_rules! impl_sign {
         ($name:ident, $fun:expr) => {
@@ -169,4 +272,409 @@ pub mod ecdsa {
     impl_sign!(sign_sha256, Hacl_P256_ecdsa_sign_p256_sha2);
     impl_sign!(sign_sha384, Hacl_P256_ecdsa_sign_p256_sha384);
     impl_sign!(sign_sha512, Hacl_P256_ecdsa_sign_p256_sha512);
+
+    macro_rules! impl_sign_deterministic {
+        ($name:ident, $fun:expr, $hash:expr) => {
+            /// Sign `msg` with `sk` using ECDSA on P256 and a deterministic
+            /// nonce derived via RFC 6979, removing the need for a
+            /// caller-supplied nonce.
+            pub fn $name(msg: &[u8], sk: &[u8; 32]) -> Result<[u8; 64], Error> {
+                let private = validate_scalar_slice(sk)?;
+
+                let mut nonces = Rfc6979::new($hash, &private, msg);
+                loop {
+                    let nonce = nonces.next();
+                    // A nonce generated by the DRBG is always in `[1, n)`, but
+                    // keep the same check the nonce-based signers use.
+                    validate_scalar(&nonce)?;
+
+                    let mut signature = [0u8; 64];
+                    let success = unsafe {
+                        $fun(
+                            signature.as_mut_ptr(),
+                            msg.len() as u32,
+                            msg.as_ptr() as _,
+                            private.as_ptr() as _,
+                            nonce.as_ptr() as _,
+                        )
+                    };
+
+                    // HACL rejects the rare candidate that yields `r == 0` or
+                    // `s == 0`; RFC 6979 says to keep drawing until one works.
+                    if success {
+                        return Ok(signature);
+                    }
+                }
+            }
+        };
+    }
+
+    impl_sign_deterministic!(
+        sign_sha256_deterministic,
+        Hacl_P256_ecdsa_sign_p256_sha2,
+        Hash::Sha256
+    );
+    impl_sign_deterministic!(
+        sign_sha384_deterministic,
+        Hacl_P256_ecdsa_sign_p256_sha384,
+        Hash::Sha384
+    );
+    impl_sign_deterministic!(
+        sign_sha512_deterministic,
+        Hacl_P256_ecdsa_sign_p256_sha512,
+        Hash::Sha512
+    );
+
+    macro_rules! impl_verify {
+        ($name:ident, $fun:expr) => {
+            /// Verify that `signature` (`r || s`) is a valid ECDSA signature of
+            /// `msg` under `public_key` (the 64 byte `X || Y`).
+            ///
+            /// Returns [`Error::InvalidPoint`] for a malformed public key and
+            /// `Ok(false)` for a well-formed signature that does not verify, so
+            /// callers can tell invalid input apart from a failed check.
+            pub fn $name(
+                msg: &[u8],
+                signature: &[u8; 64],
+                public_key: &[u8; 64],
+            ) -> Result<bool, Error> {
+                validate_point(public_key)?;
+
+                let valid = unsafe {
+                    $fun(
+                        msg.len() as u32,
+                        msg.as_ptr() as _,
+                        public_key.as_ptr() as _,
+                        signature.as_ptr() as _,
+                        signature[32..].as_ptr() as _,
+                    )
+                };
+
+                Ok(valid)
+            }
+        };
+    }
+
+    impl_verify!(verify_sha256, Hacl_P256_ecdsa_verif_p256_sha2);
+    impl_verify!(verify_sha384, Hacl_P256_ecdsa_verif_p256_sha384);
+    impl_verify!(verify_sha512, Hacl_P256_ecdsa_verif_p256_sha512);
+
+    /// Encode a fixed `r || s` signature as an ASN.1 DER `SEQUENCE` of two
+    /// `INTEGER`s, as expected by X.509, TLS and JWT (ES256) consumers.
+    pub fn signature_to_der(sig: &[u8; 64]) -> Vec<u8> {
+        let r = int_to_der(&sig[..32]);
+        let s = int_to_der(&sig[32..]);
+
+        let mut out = Vec::with_capacity(2 + r.len() + s.len());
+        out.push(0x30);
+        out.push((r.len() + s.len()) as u8);
+        out.extend_from_slice(&r);
+        out.extend_from_slice(&s);
+        out
+    }
+
+    /// Decode an ASN.1 DER ECDSA signature back into the fixed 64 byte
+    /// `r || s` form.
+    ///
+    /// The structure is validated strictly: tags and lengths must match, the
+    /// integers must be minimally encoded and non-negative, and no trailing
+    /// bytes may remain. Any violation yields [`Error::InvalidInput`].
+    pub fn signature_from_der(der: &[u8]) -> Result<[u8; 64], Error> {
+        let mut pos = 0;
+        if read_byte(der, &mut pos)? != 0x30 {
+            return Err(Error::InvalidInput);
+        }
+        let seq_len = read_len(der, &mut pos)?;
+        if pos + seq_len != der.len() {
+            return Err(Error::InvalidInput);
+        }
+
+        let r = read_integer(der, &mut pos)?;
+        let s = read_integer(der, &mut pos)?;
+        if pos != der.len() {
+            return Err(Error::InvalidInput);
+        }
+
+        let mut sig = [0u8; 64];
+        sig[..32].copy_from_slice(&r);
+        sig[32..].copy_from_slice(&s);
+        Ok(sig)
+    }
+
+    /// Encode a 32 byte big-endian scalar as the value of a DER `INTEGER`:
+    /// strip leading zero bytes, then prepend `0x00` if the high bit is set.
+    fn int_to_der(bytes: &[u8]) -> Vec<u8> {
+        let mut start = 0;
+        while start < bytes.len() - 1 && bytes[start] == 0 {
+            start += 1;
+        }
+        let mut value = bytes[start..].to_vec();
+        if value[0] & 0x80 != 0 {
+            value.insert(0, 0x00);
+        }
+
+        let mut out = Vec::with_capacity(2 + value.len());
+        out.push(0x02);
+        out.push(value.len() as u8);
+        out.extend_from_slice(&value);
+        out
+    }
+
+    fn read_byte(der: &[u8], pos: &mut usize) -> Result<u8, Error> {
+        let b = *der.get(*pos).ok_or(Error::InvalidInput)?;
+        *pos += 1;
+        Ok(b)
+    }
+
+    /// Read a definite short-form length. ECDSA signatures never need the
+    /// long form, so the high bit being set is rejected.
+    fn read_len(der: &[u8], pos: &mut usize) -> Result<usize, Error> {
+        let b = read_byte(der, pos)?;
+        if b & 0x80 != 0 {
+            return Err(Error::InvalidInput);
+        }
+        Ok(b as usize)
+    }
+
+    /// Read a DER `INTEGER` and return it left-padded to 32 bytes.
+    fn read_integer(der: &[u8], pos: &mut usize) -> Result<[u8; 32], Error> {
+        if read_byte(der, pos)? != 0x02 {
+            return Err(Error::InvalidInput);
+        }
+        let len = read_len(der, pos)?;
+        let end = pos.checked_add(len).ok_or(Error::InvalidInput)?;
+        let bytes = der.get(*pos..end).ok_or(Error::InvalidInput)?;
+        *pos = end;
+
+        if bytes.is_empty() {
+            return Err(Error::InvalidInput);
+        }
+        // Reject negative integers (high bit set) and non-minimal encodings
+        // (a leading `0x00` that is not needed to keep the value positive).
+        if bytes[0] & 0x80 != 0 {
+            return Err(Error::InvalidInput);
+        }
+        if bytes.len() > 1 && bytes[0] == 0x00 && bytes[1] & 0x80 == 0 {
+            return Err(Error::InvalidInput);
+        }
+
+        let magnitude = if bytes[0] == 0x00 { &bytes[1..] } else { bytes };
+        if magnitude.len() > 32 {
+            return Err(Error::InvalidInput);
+        }
+
+        let mut out = [0u8; 32];
+        out[32 - magnitude.len()..].copy_from_slice(magnitude);
+        Ok(out)
+    }
+
+    /// RFC 6979 deterministic nonce generation as an HMAC_DRBG over the
+    /// crate's HMAC/SHA-2 bindings.
+    mod rfc6979 {
+        use hacl_star_sys::{
+            Hacl_HMAC_compute_sha2_256, Hacl_HMAC_compute_sha2_384, Hacl_HMAC_compute_sha2_512,
+            Hacl_Hash_SHA2_hash_256, Hacl_Hash_SHA2_hash_384, Hacl_Hash_SHA2_hash_512,
+        };
+
+        /// The P256 curve order `n`, big-endian. `qlen` is 256 bits.
+        const ORDER: [u8; 32] = [
+            0xff, 0xff, 0xff, 0xff, 0x00, 0x00, 0x00, 0x00, 0xff, 0xff, 0xff, 0xff, 0xff, 0xff,
+            0xff, 0xff, 0xbc, 0xe6, 0xfa, 0xad, 0xa7, 0x17, 0x9e, 0x84, 0xf3, 0xb9, 0xca, 0xc2,
+            0xfc, 0x63, 0x25, 0x51,
+        ];
+
+        /// The hash used both to digest the message and to drive the DRBG.
+        #[derive(Clone, Copy)]
+        pub enum Hash {
+            Sha256,
+            Sha384,
+            Sha512,
+        }
+
+        impl Hash {
+            /// Output length `hlen` in bytes.
+            fn hlen(self) -> usize {
+                match self {
+                    Hash::Sha256 => 32,
+                    Hash::Sha384 => 48,
+                    Hash::Sha512 => 64,
+                }
+            }
+
+            /// `Hash(msg)`, returning the leading `hlen` bytes of the buffer.
+            fn hash(self, msg: &[u8]) -> [u8; 64] {
+                let mut out = [0u8; 64];
+                let mut m = msg.to_vec();
+                unsafe {
+                    match self {
+                        Hash::Sha256 => {
+                            Hacl_Hash_SHA2_hash_256(out.as_mut_ptr(), m.as_mut_ptr(), m.len() as u32)
+                        }
+                        Hash::Sha384 => {
+                            Hacl_Hash_SHA2_hash_384(out.as_mut_ptr(), m.as_mut_ptr(), m.len() as u32)
+                        }
+                        Hash::Sha512 => {
+                            Hacl_Hash_SHA2_hash_512(out.as_mut_ptr(), m.as_mut_ptr(), m.len() as u32)
+                        }
+                    }
+                }
+                out
+            }
+
+            /// `HMAC_key(data)`, returning the leading `hlen` bytes.
+            fn hmac(self, key: &[u8], data: &[u8]) -> [u8; 64] {
+                let mut out = [0u8; 64];
+                let mut k = key.to_vec();
+                let mut d = data.to_vec();
+                unsafe {
+                    match self {
+                        Hash::Sha256 => Hacl_HMAC_compute_sha2_256(
+                            out.as_mut_ptr(),
+                            k.as_mut_ptr(),
+                            k.len() as u32,
+                            d.as_mut_ptr(),
+                            d.len() as u32,
+                        ),
+                        Hash::Sha384 => Hacl_HMAC_compute_sha2_384(
+                            out.as_mut_ptr(),
+                            k.as_mut_ptr(),
+                            k.len() as u32,
+                            d.as_mut_ptr(),
+                            d.len() as u32,
+                        ),
+                        Hash::Sha512 => Hacl_HMAC_compute_sha2_512(
+                            out.as_mut_ptr(),
+                            k.as_mut_ptr(),
+                            k.len() as u32,
+                            d.as_mut_ptr(),
+                            d.len() as u32,
+                        ),
+                    }
+                }
+                out
+            }
+        }
+
+        /// `a >= b` for 32-byte big-endian integers.
+        fn geq(a: &[u8; 32], b: &[u8; 32]) -> bool {
+            for i in 0..32 {
+                if a[i] != b[i] {
+                    return a[i] > b[i];
+                }
+            }
+            true
+        }
+
+        /// Reduce a 32-byte big-endian value mod `n`. The input is `< 2^256`
+        /// and `n > 2^255`, so at most one subtraction is needed.
+        fn reduce(mut x: [u8; 32]) -> [u8; 32] {
+            if geq(&x, &ORDER) {
+                let mut borrow = 0i16;
+                for i in (0..32).rev() {
+                    let diff = x[i] as i16 - ORDER[i] as i16 - borrow;
+                    if diff < 0 {
+                        x[i] = (diff + 256) as u8;
+                        borrow = 1;
+                    } else {
+                        x[i] = diff as u8;
+                        borrow = 0;
+                    }
+                }
+            }
+            x
+        }
+
+        /// `bits2octets(h1)`: reduce the leftmost `qlen` bits of the digest
+        /// mod `n`. `qlen` is 256, so the leftmost 32 bytes are taken.
+        fn bits2octets(digest: &[u8; 64]) -> [u8; 32] {
+            let mut z = [0u8; 32];
+            z.copy_from_slice(&digest[..32]);
+            reduce(z)
+        }
+
+        /// `1 <= x < n`.
+        fn in_range(x: &[u8; 32]) -> bool {
+            !x.iter().all(|b| *b == 0) && !geq(x, &ORDER)
+        }
+
+        /// An RFC 6979 HMAC_DRBG instance producing P256 nonce candidates.
+        pub struct Rfc6979 {
+            hash: Hash,
+            k: [u8; 64],
+            v: [u8; 64],
+            reseed: bool,
+        }
+
+        impl Rfc6979 {
+            pub fn new(hash: Hash, sk: &[u8; 32], msg: &[u8]) -> Self {
+                let hlen = hash.hlen();
+                let h1 = hash.hash(msg);
+                let z = bits2octets(&h1);
+
+                // V = 0x01..01, K = 0x00..00 (hlen bytes each).
+                let mut v = [0u8; 64];
+                for b in v[..hlen].iter_mut() {
+                    *b = 0x01;
+                }
+                let mut k = [0u8; 64];
+
+                // K = HMAC_K(V || 0x00 || int2octets(x) || bits2octets(h1))
+                let mut seed = Vec::with_capacity(hlen + 1 + 32 + 32);
+                seed.extend_from_slice(&v[..hlen]);
+                seed.push(0x00);
+                seed.extend_from_slice(sk);
+                seed.extend_from_slice(&z);
+                k = hash.hmac(&k[..hlen], &seed);
+                // V = HMAC_K(V)
+                v = hash.hmac(&k[..hlen], &v[..hlen]);
+
+                // K = HMAC_K(V || 0x01 || int2octets(x) || bits2octets(h1))
+                let mut seed = Vec::with_capacity(hlen + 1 + 32 + 32);
+                seed.extend_from_slice(&v[..hlen]);
+                seed.push(0x01);
+                seed.extend_from_slice(sk);
+                seed.extend_from_slice(&z);
+                k = hash.hmac(&k[..hlen], &seed);
+                // V = HMAC_K(V)
+                v = hash.hmac(&k[..hlen], &v[..hlen]);
+
+                Rfc6979 {
+                    hash,
+                    k,
+                    v,
+                    reseed: false,
+                }
+            }
+
+            /// Draw the next candidate nonce, reseeding the DRBG before every
+            /// draw after the first (both on out-of-range candidates and when
+            /// the caller rejected the previous one).
+            pub fn next(&mut self) -> [u8; 32] {
+                let hlen = self.hash.hlen();
+                loop {
+                    if self.reseed {
+                        let mut seed = Vec::with_capacity(hlen + 1);
+                        seed.extend_from_slice(&self.v[..hlen]);
+                        seed.push(0x00);
+                        self.k = self.hash.hmac(&self.k[..hlen], &seed);
+                        self.v = self.hash.hmac(&self.k[..hlen], &self.v[..hlen]);
+                    }
+                    self.reseed = true;
+
+                    // T = leftmost qlen (=256) bits gathered from V.
+                    let mut t = Vec::with_capacity(32);
+                    while t.len() < 32 {
+                        self.v = self.hash.hmac(&self.k[..hlen], &self.v[..hlen]);
+                        t.extend_from_slice(&self.v[..hlen]);
+                    }
+                    let mut candidate = [0u8; 32];
+                    candidate.copy_from_slice(&t[..32]);
+
+                    if in_range(&candidate) {
+                        return candidate;
+                    }
+                }
+            }
+        }
+    }
 }
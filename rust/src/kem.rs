@@ -0,0 +1,187 @@
+//! A Diffie-Hellman based KEM (DHKEM) as specified by HPKE (RFC 9180),
+//! layered on top of the x25519 and P256 ECDH primitives.
+
+use hacl_star_sys::{Hacl_HKDF_expand_sha2_256, Hacl_HKDF_extract_sha2_256};
+
+use crate::hazmat::{curve25519, p256};
+
+/// The DHKEM output length (`Nsecret`). Both supported suites use
+/// HKDF-SHA256, which yields a 32 byte shared secret.
+const NSECRET: usize = 32;
+
+#[derive(Debug, PartialEq, Eq, Clone, Copy)]
+pub enum Error {
+    InvalidInput,
+    InvalidScalar,
+    InvalidPoint,
+}
+
+impl From<p256::Error> for Error {
+    fn from(e: p256::Error) -> Self {
+        match e {
+            p256::Error::InvalidScalar => Error::InvalidScalar,
+            p256::Error::InvalidPoint => Error::InvalidPoint,
+            _ => Error::InvalidInput,
+        }
+    }
+}
+
+impl From<curve25519::Error> for Error {
+    fn from(_: curve25519::Error) -> Self {
+        Error::InvalidInput
+    }
+}
+
+/// A DHKEM instantiation, identified by its underlying ECDH curve.
+#[derive(Debug, PartialEq, Eq, Clone, Copy)]
+pub enum Algorithm {
+    X25519,
+    P256,
+}
+
+impl Algorithm {
+    /// The two-byte KEM identifier from the HPKE registry.
+    fn kem_id(self) -> u16 {
+        match self {
+            Algorithm::X25519 => 0x0020,
+            Algorithm::P256 => 0x0010,
+        }
+    }
+
+    /// `suite_id = "KEM" || I2OSP(kem_id, 2)`.
+    fn suite_id(self) -> [u8; 5] {
+        let id = self.kem_id();
+        [b'K', b'E', b'M', (id >> 8) as u8, id as u8]
+    }
+
+    /// Generate an ephemeral key pair to the recipient and derive the shared
+    /// secret via the DHKEM encapsulation step.
+    ///
+    /// Returns `(shared_secret, enc)`, where `enc` is the serialized ephemeral
+    /// public key.
+    pub fn encapsulate<R: rand_core::CryptoRng + rand_core::RngCore>(
+        self,
+        pk: &[u8],
+        rng: &mut R,
+    ) -> Result<(Vec<u8>, Vec<u8>), Error> {
+        match self {
+            Algorithm::X25519 => {
+                let pk_r = to_array32(pk)?;
+                let (sk_e, pk_e) = curve25519::generate_key_pair(rng);
+                let dh = curve25519::ecdh(&to_array32(sk_e.as_ref())?, &pk_r)?;
+
+                let enc = pk_e.as_ref().to_vec();
+                let shared_secret = self.extract_and_expand(&dh, &enc, pk);
+                Ok((shared_secret, enc))
+            }
+            Algorithm::P256 => {
+                let pk_r = p256::uncompressed_to_coordinates(pk)?;
+                let (sk_e, pk_e) = p256::generate_key_pair(rng);
+                let dh = p256::ecdh(&to_array32(sk_e.as_ref())?, &pk_r)?;
+
+                let enc = serialize_p256(pk_e.as_ref())?;
+                // The DH shared secret is the X coordinate only.
+                let shared_secret = self.extract_and_expand(&dh[..32], &enc, pk);
+                Ok((shared_secret, enc))
+            }
+        }
+    }
+
+    /// Recover the shared secret from an encapsulation `enc` using the
+    /// recipient private key `sk`.
+    pub fn decapsulate(self, enc: &[u8], sk: &[u8]) -> Result<Vec<u8>, Error> {
+        match self {
+            Algorithm::X25519 => {
+                let sk_r = to_array32(sk)?;
+                let pk_e = to_array32(enc)?;
+                let dh = curve25519::ecdh(&sk_r, &pk_e)?;
+
+                let pk_rm = curve25519::secret_to_public(&sk_r);
+                Ok(self.extract_and_expand(&dh, enc, &pk_rm))
+            }
+            Algorithm::P256 => {
+                let sk_r = to_array32(sk)?;
+                let pk_e = p256::uncompressed_to_coordinates(enc)?;
+                let dh = p256::ecdh(&sk_r, &pk_e)?;
+
+                let pk_r = p256::secret_to_public(&sk_r)?;
+                let pk_rm = serialize_p256(&pk_r)?;
+                Ok(self.extract_and_expand(&dh[..32], enc, &pk_rm))
+            }
+        }
+    }
+
+    /// The DHKEM `ExtractAndExpand` step over `kem_context = enc || pkRm`.
+    fn extract_and_expand(self, dh: &[u8], enc: &[u8], pk_rm: &[u8]) -> Vec<u8> {
+        let suite_id = self.suite_id();
+
+        let eae_prk = labeled_extract(&suite_id, b"eae_prk", dh);
+
+        let mut kem_context = Vec::with_capacity(enc.len() + pk_rm.len());
+        kem_context.extend_from_slice(enc);
+        kem_context.extend_from_slice(pk_rm);
+
+        labeled_expand(&suite_id, &eae_prk, b"shared_secret", &kem_context, NSECRET)
+    }
+}
+
+/// `LabeledExtract("", label, ikm)` for HKDF-SHA256.
+fn labeled_extract(suite_id: &[u8], label: &[u8], ikm: &[u8]) -> [u8; 32] {
+    let mut labeled_ikm = Vec::with_capacity(7 + suite_id.len() + label.len() + ikm.len());
+    labeled_ikm.extend_from_slice(b"HPKE-v1");
+    labeled_ikm.extend_from_slice(suite_id);
+    labeled_ikm.extend_from_slice(label);
+    labeled_ikm.extend_from_slice(ikm);
+
+    let mut prk = [0u8; 32];
+    unsafe {
+        Hacl_HKDF_extract_sha2_256(
+            prk.as_mut_ptr(),
+            core::ptr::null_mut(),
+            0,
+            labeled_ikm.as_mut_ptr(),
+            labeled_ikm.len() as u32,
+        )
+    };
+    prk
+}
+
+/// `LabeledExpand(prk, label, info, len)` for HKDF-SHA256.
+fn labeled_expand(suite_id: &[u8], prk: &[u8; 32], label: &[u8], info: &[u8], len: usize) -> Vec<u8> {
+    let mut labeled_info = Vec::with_capacity(2 + 7 + suite_id.len() + label.len() + info.len());
+    labeled_info.extend_from_slice(&(len as u16).to_be_bytes());
+    labeled_info.extend_from_slice(b"HPKE-v1");
+    labeled_info.extend_from_slice(suite_id);
+    labeled_info.extend_from_slice(label);
+    labeled_info.extend_from_slice(info);
+
+    let mut okm = vec![0u8; len];
+    let mut prk = *prk;
+    unsafe {
+        Hacl_HKDF_expand_sha2_256(
+            okm.as_mut_ptr(),
+            prk.as_mut_ptr(),
+            prk.len() as u32,
+            labeled_info.as_mut_ptr(),
+            labeled_info.len() as u32,
+            len as u32,
+        )
+    };
+    okm
+}
+
+/// Serialize a raw P256 public key (`X || Y`) into the uncompressed SEC1
+/// encoding (`0x04 || X || Y`) used as the DHKEM `enc`.
+fn serialize_p256(point: &[u8]) -> Result<Vec<u8>, Error> {
+    if point.len() != 64 {
+        return Err(Error::InvalidInput);
+    }
+    let mut out = Vec::with_capacity(65);
+    out.push(0x04);
+    out.extend_from_slice(point);
+    Ok(out)
+}
+
+fn to_array32(bytes: &[u8]) -> Result<[u8; 32], Error> {
+    bytes.try_into().map_err(|_| Error::InvalidInput)
+}